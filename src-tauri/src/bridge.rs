@@ -0,0 +1,208 @@
+use std::time::Duration;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+use tauri_plugin_shell::ShellExt;
+
+use crate::broadcast::broadcast_to_windows;
+
+/// Windows that want live NMEA/position data: the main chart view and the
+/// instrument panel. Fixes come in fast, so these go through
+/// `broadcast_to_windows` rather than an `emit` per window.
+const NMEA_DATA_WINDOWS: &[&str] = &["main", "instruments"];
+
+/// Lifecycle of the NMEA bridge sidecar, broadcast to the UI on every change.
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BridgeStatus {
+    Running,
+    Stopped,
+    Crashed,
+}
+
+/// Managed state holding the running sidecar child, if any.
+#[derive(Default)]
+pub struct BridgeState(Mutex<Inner>);
+
+#[derive(Default)]
+struct Inner {
+    child: Option<CommandChild>,
+    /// Set by `bridge_stop` so the exit handler doesn't treat a deliberate
+    /// shutdown as a crash and try to restart the sidecar.
+    stopping: bool,
+    restart_attempts: u32,
+}
+
+/// Cap on the exponential restart backoff: restarts wait 2, 4, 8 and 16
+/// seconds, then 30 seconds for every later attempt.
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(30);
+
+fn emit_status(app: &AppHandle, status: BridgeStatus) {
+    let _ = app.emit("bridge://status", status);
+}
+
+#[tauri::command]
+pub fn bridge_start(app: AppHandle, state: State<'_, BridgeState>) -> Result<(), String> {
+    spawn_bridge(&app, &state, true)
+}
+
+#[tauri::command]
+pub fn bridge_stop(app: AppHandle, state: State<'_, BridgeState>) -> Result<(), String> {
+    stop_bridge(&app, &state)
+}
+
+#[tauri::command]
+pub fn bridge_status(state: State<'_, BridgeState>) -> BridgeStatus {
+    if state.0.lock().unwrap().child.is_some() {
+        BridgeStatus::Running
+    } else {
+        BridgeStatus::Stopped
+    }
+}
+
+/// Decide, under the lock, whether this call should actually spawn a child,
+/// and update `stopping`/bookkeeping accordingly. `explicit` is true for
+/// `bridge_start` (always clears `stopping`) and false for the auto-restart
+/// path woken from backoff (bails out if `bridge_stop` ran in the meantime).
+/// Pulled out of `spawn_bridge` so the start/stop race can be unit tested
+/// without a running sidecar.
+fn begin_spawn(inner: &mut Inner, explicit: bool) -> bool {
+    if inner.child.is_some() {
+        return false;
+    }
+    if explicit {
+        inner.stopping = false;
+    } else if inner.stopping {
+        return false;
+    }
+    true
+}
+
+fn spawn_bridge(app: &AppHandle, state: &State<'_, BridgeState>, explicit: bool) -> Result<(), String> {
+    {
+        let mut inner = state.0.lock().unwrap();
+        if !begin_spawn(&mut inner, explicit) {
+            return Ok(());
+        }
+    }
+
+    let (mut rx, child) = app
+        .shell()
+        .sidecar("nmea-bridge")
+        .map_err(|e| e.to_string())?
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    // `bridge_stop` could have run while the sidecar process was spawning;
+    // don't let a child slip in after a deliberate stop.
+    let killed_after_spawn = {
+        let mut inner = state.0.lock().unwrap();
+        if inner.stopping {
+            Some(child)
+        } else {
+            inner.child = Some(child);
+            None
+        }
+    };
+    if let Some(child) = killed_after_spawn {
+        let _ = child.kill();
+        return Ok(());
+    }
+
+    emit_status(app, BridgeStatus::Running);
+
+    // Watch the sidecar's event stream so a crash is noticed and, unless we
+    // asked it to stop, retried with backoff instead of silently going dark.
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(line) => {
+                    let sentence = String::from_utf8_lossy(&line).into_owned();
+                    broadcast_to_windows(&app_handle, "nmea://data", sentence, NMEA_DATA_WINDOWS);
+                }
+                CommandEvent::Terminated(_) => {
+                    on_bridge_exit(&app_handle);
+                    break;
+                }
+                _ => {}
+            }
+        }
+    });
+
+    Ok(())
+}
+
+fn on_bridge_exit(app: &AppHandle) {
+    let state = app.state::<BridgeState>();
+    let attempts = {
+        let mut inner = state.0.lock().unwrap();
+        inner.child = None;
+        if inner.stopping {
+            None
+        } else {
+            inner.restart_attempts += 1;
+            Some(inner.restart_attempts)
+        }
+    };
+
+    let Some(attempts) = attempts else {
+        emit_status(app, BridgeStatus::Stopped);
+        return;
+    };
+
+    emit_status(app, BridgeStatus::Crashed);
+
+    let backoff = Duration::from_secs(2u64.saturating_pow(attempts.min(5))).min(MAX_RESTART_BACKOFF);
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(backoff).await;
+        let state = app_handle.state::<BridgeState>();
+        let _ = spawn_bridge(&app_handle, &state, false);
+    });
+}
+
+/// Kill the sidecar through its child handle. Cross-platform, unlike the old
+/// `taskkill /F /IM nmea-bridge.exe`, and called both from `bridge_stop` and
+/// from the app's `RunEvent::Exit` handler.
+pub fn stop_bridge(app: &AppHandle, state: &State<'_, BridgeState>) -> Result<(), String> {
+    let mut inner = state.0.lock().unwrap();
+    inner.stopping = true;
+    let Some(child) = inner.child.take() else {
+        return Ok(());
+    };
+    drop(inner);
+
+    child.kill().map_err(|e| e.to_string())?;
+    emit_status(app, BridgeStatus::Stopped);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_start_clears_stopping_and_spawns() {
+        let mut inner = Inner { stopping: true, ..Default::default() };
+        assert!(begin_spawn(&mut inner, true));
+        assert!(!inner.stopping);
+    }
+
+    #[test]
+    fn auto_restart_is_skipped_while_stopping() {
+        // Reproduces the crash-then-stop race: the sidecar died, backoff is
+        // pending, and `bridge_stop` runs before the backoff sleep wakes up.
+        let mut inner = Inner { stopping: true, ..Default::default() };
+        assert!(!begin_spawn(&mut inner, false));
+        assert!(inner.stopping, "a deliberate stop must not be cleared by a pending auto-restart");
+    }
+
+    #[test]
+    fn auto_restart_spawns_when_not_stopping() {
+        let mut inner = Inner::default();
+        assert!(begin_spawn(&mut inner, false));
+    }
+}
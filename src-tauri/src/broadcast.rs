@@ -0,0 +1,46 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, EventTarget};
+
+/// Broadcast one payload to a caller-supplied set of windows, serializing it
+/// exactly once instead of once per recipient. Intended for high-frequency
+/// events (live NMEA/position/instrument updates) where emitting a separate
+/// copy per window would duplicate serialization work on every fix.
+pub fn broadcast_to_windows<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S, windows: &[&str]) {
+    let _ = app.emit_filter(event, payload, |target| target_matches(target, windows));
+}
+
+/// `emit_filter`'s predicate runs over `EventTarget`, not a bare window
+/// label, so pull the label back out of whichever labeled variant it is.
+fn target_matches(target: &EventTarget, windows: &[&str]) -> bool {
+    let label = match target {
+        EventTarget::Window { label }
+        | EventTarget::Webview { label }
+        | EventTarget::WebviewWindow { label }
+        | EventTarget::AnyLabel { label } => label.as_str(),
+        EventTarget::App | EventTarget::Any => return false,
+    };
+    windows.contains(&label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_a_window_target_in_the_list() {
+        let target = EventTarget::WebviewWindow { label: "main".into() };
+        assert!(target_matches(&target, &["main", "instruments"]));
+    }
+
+    #[test]
+    fn rejects_a_window_target_not_in_the_list() {
+        let target = EventTarget::WebviewWindow { label: "settings".into() };
+        assert!(!target_matches(&target, &["main", "instruments"]));
+    }
+
+    #[test]
+    fn rejects_non_labeled_targets() {
+        assert!(!target_matches(&EventTarget::App, &["main"]));
+        assert!(!target_matches(&EventTarget::Any, &["main"]));
+    }
+}
@@ -0,0 +1,191 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+/// Directories the frontend is allowed to write into via `save_file`,
+/// populated in `setup` from the standard logical locations below.
+#[derive(Default)]
+pub struct ScopeState(Mutex<Vec<PathBuf>>);
+
+impl ScopeState {
+    pub fn new(dirs: Vec<PathBuf>) -> Self {
+        Self(Mutex::new(dirs))
+    }
+
+    fn allows(&self, dir: &Path) -> bool {
+        self.0.lock().unwrap().iter().any(|allowed| dir.starts_with(allowed))
+    }
+}
+
+/// Logical save locations exposed to the frontend instead of raw paths, so
+/// the common "export logbook" flow never needs to build an absolute path.
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SaveLocation {
+    Documents,
+    Exports,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum SaveFileError {
+    OutsideScope,
+    Io(String),
+}
+
+impl std::fmt::Display for SaveFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveFileError::OutsideScope => write!(f, "path is outside the allowed save locations"),
+            SaveFileError::Io(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SaveFileError {}
+
+/// Refuse to write through an existing symlink. `fs::write` follows
+/// symlinks, so a symlink planted inside an otherwise-allowed directory
+/// (e.g. `~/Documents/export`) could point outside the scope and silently
+/// defeat the allow-list checked above it.
+fn reject_existing_symlink(path: &Path) -> Result<(), SaveFileError> {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_symlink() => Err(SaveFileError::OutsideScope),
+        _ => Ok(()),
+    }
+}
+
+fn resolve_exports_dir(app: &AppHandle) -> Result<PathBuf, SaveFileError> {
+    app.path()
+        .app_local_data_dir()
+        .map(|dir| dir.join("exports"))
+        .map_err(|e| SaveFileError::Io(e.to_string()))
+}
+
+/// The directories `save_file` and `save_file_to_scope` are allowed to write
+/// into: the user's Documents folder and our own exports directory.
+pub fn default_scope(app: &AppHandle) -> Result<Vec<PathBuf>, SaveFileError> {
+    let mut dirs = Vec::new();
+    if let Ok(documents) = app.path().document_dir() {
+        dirs.push(documents);
+    }
+    dirs.push(resolve_exports_dir(app)?);
+    Ok(dirs)
+}
+
+/// Write bytes to a user-chosen absolute path (from the native save dialog),
+/// rejecting anything outside the configured scope instead of writing
+/// wherever the frontend asks.
+#[tauri::command]
+pub fn save_file(state: State<'_, ScopeState>, path: String, data: Vec<u8>) -> Result<(), SaveFileError> {
+    let requested = PathBuf::from(&path);
+    let parent = requested.parent().unwrap_or_else(|| Path::new("."));
+    let canonical_parent = parent
+        .canonicalize()
+        .map_err(|e| SaveFileError::Io(e.to_string()))?;
+
+    if !state.allows(&canonical_parent) {
+        return Err(SaveFileError::OutsideScope);
+    }
+
+    let file_name = requested.file_name().ok_or(SaveFileError::OutsideScope)?;
+    let target = canonical_parent.join(file_name);
+    reject_existing_symlink(&target)?;
+    std::fs::write(&target, &data).map_err(|e| SaveFileError::Io(e.to_string()))
+}
+
+/// Write bytes into one of the app's logical save locations by filename
+/// only, so callers like "export logbook" never need a raw absolute path.
+#[tauri::command]
+pub fn save_file_to_scope(
+    app: AppHandle,
+    location: SaveLocation,
+    filename: String,
+    data: Vec<u8>,
+) -> Result<(), SaveFileError> {
+    let dir = match location {
+        SaveLocation::Documents => app
+            .path()
+            .document_dir()
+            .map_err(|e| SaveFileError::Io(e.to_string()))?,
+        SaveLocation::Exports => resolve_exports_dir(&app)?,
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| SaveFileError::Io(e.to_string()))?;
+
+    let file_name = Path::new(&filename)
+        .file_name()
+        .ok_or(SaveFileError::OutsideScope)?;
+    let target = dir.join(file_name);
+    reject_existing_symlink(&target)?;
+    std::fs::write(&target, &data).map_err(|e| SaveFileError::Io(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_the_scoped_directory_itself() {
+        let scope = ScopeState::new(vec![PathBuf::from("/home/user/Documents")]);
+        assert!(scope.allows(Path::new("/home/user/Documents")));
+    }
+
+    #[test]
+    fn allows_a_subdirectory_of_a_scoped_directory() {
+        let scope = ScopeState::new(vec![PathBuf::from("/home/user/Documents")]);
+        assert!(scope.allows(Path::new("/home/user/Documents/logs/2026")));
+    }
+
+    #[test]
+    fn rejects_an_unrelated_directory() {
+        let scope = ScopeState::new(vec![PathBuf::from("/home/user/Documents")]);
+        assert!(!scope.allows(Path::new("/etc")));
+    }
+
+    #[test]
+    fn rejects_a_sibling_that_merely_shares_a_string_prefix() {
+        // "/home/user/Documents-evil" starts with the string "/home/user/Documents"
+        // but is not a path under it — component-wise starts_with must reject it.
+        let scope = ScopeState::new(vec![PathBuf::from("/home/user/Documents")]);
+        assert!(!scope.allows(Path::new("/home/user/Documents-evil")));
+    }
+
+    #[test]
+    fn rejects_when_no_directories_are_scoped() {
+        let scope = ScopeState::default();
+        assert!(!scope.allows(Path::new("/home/user/Documents")));
+    }
+
+    #[test]
+    fn rejects_writing_through_an_existing_symlink() {
+        let dir = std::env::temp_dir().join(format!("fs_scope_symlink_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let outside_target = dir.join("outside_target");
+        std::fs::write(&outside_target, b"x").unwrap();
+        let link = dir.join("export");
+
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&outside_target, &link).unwrap();
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_file(&outside_target, &link).unwrap();
+
+        assert!(reject_existing_symlink(&link).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn allows_a_path_that_is_not_a_symlink() {
+        let dir = std::env::temp_dir().join(format!("fs_scope_plain_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("plain.txt");
+
+        assert!(reject_existing_symlink(&target).is_ok(), "a not-yet-existing path is fine");
+        std::fs::write(&target, b"x").unwrap();
+        assert!(reject_existing_symlink(&target).is_ok(), "a plain file is fine");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
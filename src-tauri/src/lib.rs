@@ -1,96 +1,83 @@
 use tauri::Manager;
 
-/// Write arbitrary bytes to a user-chosen path (called after the native save dialog).
-#[tauri::command]
-fn save_file(path: String, data: Vec<u8>) -> Result<(), String> {
-    std::fs::write(&path, &data).map_err(|e| e.to_string())
-}
+mod bridge;
+mod broadcast;
+mod fs_scope;
+mod net;
+mod startup;
+mod tiles;
+mod update;
+mod webview;
 
-/// Kill the NMEA bridge sidecar process by name.
-/// Called from JS before installing an update so the installer can overwrite nmea-bridge.exe.
-#[tauri::command]
-fn kill_bridge() {
-    #[cfg(target_os = "windows")]
-    {
-        let _ = std::process::Command::new("taskkill")
-            .args(["/F", "/IM", "nmea-bridge.exe"])
-            .output();
-    }
-}
+const BUNDLE_IDENTIFIER: &str = "com.flowpro.logbuch";
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let app = tauri::Builder::default()
-        .plugin(tauri_plugin_shell::init())
-        .plugin(tauri_plugin_updater::Builder::new().build())
-        .plugin(tauri_plugin_process::init())
-        .plugin(tauri_plugin_dialog::init())
-        .plugin(tauri_plugin_window_state::Builder::default().build())
-        .invoke_handler(tauri::generate_handler![save_file, kill_bridge])
-        .setup(|app| {
-            // ── Windows: clear WebView2 HTTP cache before the webview starts ─────
-            // WebView2 aggressively caches index.html and JS assets from the
-            // tauri:// custom protocol. After an app update the new binary runs
-            // but the old cached index.html (pointing to old JS hashes) is still
-            // served, so the UI shows an outdated version. Deleting only the HTTP
-            // response cache (Cache/ and Code Cache/) before the webview is created
-            // forces a fresh load from the embedded binary assets. IndexedDB,
-            // localStorage, and the Service Worker CacheStorage (pre-downloaded map
-            // tiles) are in separate subdirectories and are NOT touched.
-            #[cfg(target_os = "windows")]
-            {
-                if let Ok(data_dir) = app.path().app_local_data_dir() {
-                    for entry in &["Cache", "Code Cache"] {
-                        // Try direct layout (observed: %LocalAppData%\com.flowpro.logbuch\Cache)
-                        let path_direct = data_dir.join(entry);
-                        if path_direct.exists() {
-                            let _ = std::fs::remove_dir_all(&path_direct);
-                        }
-                        // Also try EBWebView\Default layout (fallback for other WRY versions)
-                        let path_ebwv = data_dir.join("EBWebView").join("Default").join(entry);
-                        if path_ebwv.exists() {
-                            let _ = std::fs::remove_dir_all(&path_ebwv);
-                        }
-                    }
-                }
-            }
+    // Scope WebView2's user-data folder to this bundle identifier before the
+    // webview is created. Ordinary restarts reuse this same folder so data
+    // persists; only a retry after a "resource in use" error below falls
+    // back to a disambiguated one.
+    let local_data_dir = dirs::data_local_dir();
+    if let Some(dir) = &local_data_dir {
+        startup::configure_data_directory(BUNDLE_IDENTIFIER, dir, None);
+    }
 
-            // ── Unregister stale SWs + clear Workbox caches via JS eval ─────────
-            // Runs after page load. Belt-and-suspenders alongside the Rust cache
-            // clear above. Preserves 'protomaps-tiles-precache' (user tile downloads).
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.eval(concat!(
-                    "(async()=>{",
-                    "var c=false;",
-                    "if('serviceWorker'in navigator){",
-                    "var r=await navigator.serviceWorker.getRegistrations();",
-                    "for(var i=0;i<r.length;i++){await r[i].unregister();c=true;}",
-                    "}",
-                    "if('caches'in window){",
-                    "var k=await caches.keys();",
-                    "for(var i=0;i<k.length;i++){",
-                    "if(k[i]!=='protomaps-tiles-precache'){await caches.delete(k[i]);c=true;}",
-                    "}",
-                    "}",
-                    "if(c&&!sessionStorage.__tc){sessionStorage.__tc='1';location.reload();}",
-                    "})();"
-                ));
+    let app = startup::build_with_retry(
+        || {
+            tiles::register_protocol(tauri::Builder::default())
+                .plugin(tauri_plugin_shell::init())
+                .plugin(tauri_plugin_updater::Builder::new().build())
+                .plugin(tauri_plugin_process::init())
+                .plugin(tauri_plugin_dialog::init())
+                .plugin(tauri_plugin_window_state::Builder::default().build())
+                .manage(bridge::BridgeState::default())
+                .manage(net::ProxyState::default())
+                .invoke_handler(tauri::generate_handler![
+                    fs_scope::save_file,
+                    fs_scope::save_file_to_scope,
+                    bridge::bridge_start,
+                    bridge::bridge_stop,
+                    bridge::bridge_status,
+                    tiles::tiles_download,
+                    tiles::tiles_cache_status,
+                    tiles::tiles_clear,
+                    net::set_proxy_override,
+                    net::get_proxy_override,
+                    webview::reload_webview,
+                    update::install_update_and_reload,
+                ])
+                .setup(|app| {
+                    app.manage(tiles::TilesState::new(
+                        app.path().app_local_data_dir()?.join("tile-cache"),
+                    ));
+                    app.manage(fs_scope::ScopeState::new(fs_scope::default_scope(
+                        &app.handle().clone(),
+                    )?));
+                    Ok(())
+                })
+                .build(tauri::generate_context!())
+        },
+        |attempt| {
+            // The default folder is the one that's locked; give the next
+            // attempt a folder of its own instead of retrying the same path.
+            if let Some(dir) = &local_data_dir {
+                startup::configure_data_directory(
+                    BUNDLE_IDENTIFIER,
+                    dir,
+                    Some(&format!("retry-{}-{attempt}", std::process::id())),
+                );
             }
-            Ok(())
-        })
-        .build(tauri::generate_context!())
-        .expect("error while building Logbuch");
+        },
+    );
 
-    app.run(|_app_handle, event| {
-        // On Windows: kill nmea-bridge.exe on app exit so the NSIS updater
-        // can overwrite the file (Windows locks running executables).
+    app.run(|app_handle, event| {
+        // Kill the NMEA bridge sidecar on app exit so the updater can
+        // overwrite its binary (the OS otherwise keeps it locked while the
+        // process is alive). Goes through the child handle, so it works the
+        // same way on every platform instead of just Windows via taskkill.
         if let tauri::RunEvent::Exit = event {
-            #[cfg(target_os = "windows")]
-            {
-                let _ = std::process::Command::new("taskkill")
-                    .args(["/F", "/IM", "nmea-bridge.exe"])
-                    .output();
-            }
+            let state = app_handle.state::<bridge::BridgeState>();
+            let _ = bridge::stop_bridge(app_handle, &state);
         }
     });
 }
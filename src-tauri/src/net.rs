@@ -0,0 +1,79 @@
+use std::sync::Mutex;
+
+use tauri::State;
+
+/// Explicit proxy override for outbound downloads, set from the settings UI.
+/// `None` means "use whatever the environment says".
+#[derive(Default)]
+pub struct ProxyState(Mutex<Option<String>>);
+
+#[tauri::command]
+pub fn set_proxy_override(state: State<'_, ProxyState>, url: Option<String>) -> Result<(), String> {
+    set_override(&state, url)
+}
+
+#[tauri::command]
+pub fn get_proxy_override(state: State<'_, ProxyState>) -> Option<String> {
+    state.0.lock().unwrap().clone()
+}
+
+/// Validate the URL before storing it. A typo'd proxy is exactly the
+/// failure mode this feature targets (crews with no direct route), so a bad
+/// override must be rejected here rather than silently falling back to "no
+/// proxy" later in `http_client`. Pulled out of the command so it can be
+/// unit tested without a running app.
+fn set_override(state: &ProxyState, url: Option<String>) -> Result<(), String> {
+    if let Some(url) = &url {
+        reqwest::Proxy::all(url).map_err(|e| format!("invalid proxy URL: {e}"))?;
+    }
+    *state.0.lock().unwrap() = url;
+    Ok(())
+}
+
+/// Build an HTTP client for downloads (tile fetches today, the updater
+/// later) that honors an explicit settings override first, then falls back
+/// to reqwest's default handling of `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY`
+/// from the environment, including `socks5://`. Without this, crews behind a
+/// satellite or shared-cabin SOCKS proxy can't pre-download tiles.
+///
+/// The override was already validated by `set_proxy_override`, so a parse
+/// failure here would mean the stored value was tampered with out-of-band;
+/// fail loudly instead of quietly downloading unproxied.
+pub fn http_client(state: &ProxyState) -> Result<reqwest::Client, String> {
+    let builder = reqwest::Client::builder();
+    let builder = match state.0.lock().unwrap().clone() {
+        Some(url) => {
+            let proxy = reqwest::Proxy::all(&url).map_err(|e| format!("invalid proxy URL: {e}"))?;
+            builder.proxy(proxy)
+        }
+        None => builder,
+    };
+    builder.build().map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_malformed_proxy_url() {
+        let state = ProxyState::default();
+        assert!(set_override(&state, Some("not a url".into())).is_err());
+        assert_eq!(*state.0.lock().unwrap(), None, "a rejected override must not be stored");
+    }
+
+    #[test]
+    fn accepts_and_stores_socks5_proxy_url() {
+        let state = ProxyState::default();
+        assert!(set_override(&state, Some("socks5://localhost:1080".into())).is_ok());
+        assert_eq!(*state.0.lock().unwrap(), Some("socks5://localhost:1080".to_string()));
+    }
+
+    #[test]
+    fn none_clears_the_override() {
+        let state = ProxyState::default();
+        set_override(&state, Some("socks5://localhost:1080".into())).unwrap();
+        assert!(set_override(&state, None).is_ok());
+        assert_eq!(*state.0.lock().unwrap(), None);
+    }
+}
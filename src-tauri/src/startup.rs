@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// HRESULTs users hit at startup when a previous instance (or this one,
+/// relaunched too fast) hasn't released its WebView2 user-data folder yet:
+/// "resource in use" and "invalid state", both previously surfaced as a
+/// hard `.expect()` panic.
+const RETRYABLE_HRESULTS: [&str; 2] = ["0x800700AA", "0x8007139F"];
+
+const MAX_ATTEMPTS: u32 = 5;
+const RETRY_DELAY: Duration = Duration::from_millis(400);
+
+/// Folder name for the WebView2 user-data directory. With no `suffix` this
+/// is just the bundle identifier, so ordinary restarts keep reusing the same
+/// folder (cookies, localStorage, IndexedDB, the tile cache all persist). A
+/// `suffix` is only used to dodge an already-locked default folder.
+fn data_dir_name(identifier: &str, suffix: Option<&str>) -> String {
+    match suffix {
+        Some(suffix) => format!("{identifier}-{suffix}"),
+        None => identifier.to_string(),
+    }
+}
+
+/// Point WebView2 at a data directory scoped to this bundle identifier,
+/// optionally disambiguated with `suffix` when the default folder is locked
+/// by another running instance.
+pub fn configure_data_directory(identifier: &str, local_data_dir: &Path, suffix: Option<&str>) {
+    let dir: PathBuf = local_data_dir
+        .join("webview2-data")
+        .join(data_dir_name(identifier, suffix));
+    std::env::set_var("WEBVIEW2_USER_DATA_FOLDER", dir);
+}
+
+/// Build the app, retrying a bounded number of times when webview creation
+/// fails with one of the known "resource in use" errors, instead of
+/// panicking. `on_retry` is called before each retry (attempt number passed
+/// in) so the caller can fall back to a disambiguated data directory if the
+/// default one turns out to be the thing that's locked. Shows a user-facing
+/// dialog and exits once attempts run out.
+pub fn build_with_retry<R: tauri::Runtime>(
+    mut make: impl FnMut() -> tauri::Result<tauri::App<R>>,
+    mut on_retry: impl FnMut(u32),
+) -> tauri::App<R> {
+    for attempt in 1..=MAX_ATTEMPTS {
+        match make() {
+            Ok(app) => return app,
+            Err(err) if attempt < MAX_ATTEMPTS && is_retryable_message(&err.to_string()) => {
+                on_retry(attempt);
+                std::thread::sleep(RETRY_DELAY * attempt);
+            }
+            Err(err) => {
+                rfd::MessageDialog::new()
+                    .set_title("Logbuch failed to start")
+                    .set_description(&format!(
+                        "The webview could not be created: {err}\n\n\
+                         Close any other running copies of Logbuch and try again."
+                    ))
+                    .set_level(rfd::MessageLevel::Error)
+                    .show();
+                std::process::exit(1);
+            }
+        }
+    }
+    unreachable!("the loop above always returns or exits the process")
+}
+
+fn is_retryable_message(msg: &str) -> bool {
+    RETRYABLE_HRESULTS.iter().any(|code| msg.contains(code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_dir_name_is_identifier_only() {
+        // Ordinary restarts must keep reusing the same folder, or every
+        // launch fragments cookies/localStorage/IndexedDB/the tile cache.
+        assert_eq!(data_dir_name("com.flowpro.logbuch", None), "com.flowpro.logbuch");
+    }
+
+    #[test]
+    fn suffix_only_applies_on_retry() {
+        assert_eq!(
+            data_dir_name("com.flowpro.logbuch", Some("retry-1")),
+            "com.flowpro.logbuch-retry-1"
+        );
+    }
+
+    #[test]
+    fn retryable_hresults_are_recognized() {
+        assert!(is_retryable_message("The requested resource is in use. (0x800700AA)"));
+        assert!(is_retryable_message("creation failed: 0x8007139F"));
+    }
+
+    #[test]
+    fn unrelated_errors_are_not_retried() {
+        assert!(!is_retryable_message("some other failure"));
+    }
+}
@@ -0,0 +1,163 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Managed state for the on-disk tile cache: just the resolved cache
+/// directory, computed once in `setup` from `app_local_data_dir`.
+pub struct TilesState(Mutex<PathBuf>);
+
+impl TilesState {
+    pub fn new(cache_dir: PathBuf) -> Self {
+        Self(Mutex::new(cache_dir))
+    }
+
+    fn dir(&self) -> PathBuf {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct TilesProgress {
+    done: usize,
+    total: usize,
+    bytes: u64,
+}
+
+#[derive(Serialize)]
+pub struct TilesCacheStatus {
+    file_count: usize,
+    total_bytes: u64,
+}
+
+/// Key a tile's cache file by the hex md5 digest of its URL, so the same URL
+/// always resolves to the same file.
+fn cache_key(url: &str) -> String {
+    format!("{:x}", md5::compute(url.as_bytes()))
+}
+
+/// Download the given tile URLs into the on-disk cache, skipping anything
+/// already cached, and emit `tiles://progress` after each one so the UI can
+/// drive a download bar for the selected bounding box.
+#[tauri::command]
+pub async fn tiles_download(
+    app: AppHandle,
+    state: State<'_, TilesState>,
+    proxy_state: State<'_, super::net::ProxyState>,
+    urls: Vec<String>,
+) -> Result<(), String> {
+    let cache_dir = state.dir();
+    std::fs::create_dir_all(&cache_dir).map_err(|e| e.to_string())?;
+
+    let client = super::net::http_client(&proxy_state)?;
+    let total = urls.len();
+
+    for (done, url) in urls.into_iter().enumerate() {
+        let dest = cache_dir.join(cache_key(&url));
+        if !dest.exists() {
+            let bytes = client
+                .get(&url)
+                .send()
+                .await
+                .map_err(|e| e.to_string())?
+                .error_for_status()
+                .map_err(|e| e.to_string())?
+                .bytes()
+                .await
+                .map_err(|e| e.to_string())?;
+            std::fs::write(&dest, &bytes).map_err(|e| e.to_string())?;
+        }
+
+        let _ = app.emit(
+            "tiles://progress",
+            TilesProgress {
+                done: done + 1,
+                total,
+                bytes: dest.metadata().map(|m| m.len()).unwrap_or(0),
+            },
+        );
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn tiles_cache_status(state: State<'_, TilesState>) -> TilesCacheStatus {
+    let mut file_count = 0;
+    let mut total_bytes = 0;
+    if let Ok(entries) = std::fs::read_dir(state.dir()) {
+        for entry in entries.flatten() {
+            if let Ok(meta) = entry.metadata() {
+                if meta.is_file() {
+                    file_count += 1;
+                    total_bytes += meta.len();
+                }
+            }
+        }
+    }
+    TilesCacheStatus { file_count, total_bytes }
+}
+
+#[tauri::command]
+pub fn tiles_clear(state: State<'_, TilesState>) -> Result<(), String> {
+    let dir = state.dir();
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).map_err(|e| e.to_string())?;
+    }
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())
+}
+
+/// Serves a previously-downloaded tile back to the webview under
+/// `tile://localhost/<url-encoded original tile url>` (the tile URL is taken
+/// from the request path), reading straight from the
+/// content-addressed cache instead of relying on CacheStorage.
+pub fn register_protocol<R: tauri::Runtime>(
+    builder: tauri::Builder<R>,
+) -> tauri::Builder<R> {
+    builder.register_uri_scheme_protocol("tile", |app, request| {
+        let state = app.state::<TilesState>();
+        let requested = request.uri().path().trim_start_matches('/');
+        let url = urlencoding::decode(requested).unwrap_or_default().into_owned();
+        let path = state.dir().join(cache_key(&url));
+
+        match std::fs::read(&path) {
+            Ok(bytes) => tauri::http::Response::builder()
+                .status(200)
+                .header("Content-Type", "application/octet-stream")
+                .body(bytes)
+                .unwrap(),
+            Err(_) => tauri::http::Response::builder()
+                .status(404)
+                .body(Vec::new())
+                .unwrap(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_url_always_maps_to_the_same_key() {
+        let url = "https://tiles.example/z/3/4/5.png";
+        assert_eq!(cache_key(url), cache_key(url));
+    }
+
+    #[test]
+    fn different_urls_map_to_different_keys() {
+        assert_ne!(
+            cache_key("https://tiles.example/z/3/4/5.png"),
+            cache_key("https://tiles.example/z/3/4/6.png")
+        );
+    }
+
+    #[test]
+    fn key_is_filesystem_safe() {
+        // No '/', ':', or query-string characters should survive into the
+        // on-disk filename, since the key is used directly as a path segment.
+        let key = cache_key("https://tiles.example/z/3/4/5.png?token=abc");
+        assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+}
@@ -0,0 +1,28 @@
+use tauri::{AppHandle, State};
+use tauri_plugin_updater::UpdaterExt;
+
+use crate::bridge;
+
+/// Check for, download, and install an update, then do the post-update
+/// refresh with a real platform reload instead of the old cache-deletion +
+/// service-worker-unregister dance. The frontend calls this one command
+/// instead of driving the updater plugin and `reload_webview` separately.
+#[tauri::command]
+pub async fn install_update_and_reload(
+    app: AppHandle,
+    bridge_state: State<'_, bridge::BridgeState>,
+) -> Result<(), String> {
+    let updater = app.updater().map_err(|e| e.to_string())?;
+    if let Some(update) = updater.check().await.map_err(|e| e.to_string())? {
+        // The installer needs to overwrite nmea-bridge.exe, which stays
+        // locked while the sidecar is running (and, since chunk0-1, it can
+        // auto-restart on its own) — stop it first, same as `RunEvent::Exit`.
+        bridge::stop_bridge(&app, &bridge_state)?;
+        update
+            .download_and_install(|_, _| {}, || {})
+            .await
+            .map_err(|e| e.to_string())?;
+        crate::webview::reload_main_window(&app)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+/// Reload the main window at the platform level instead of the JS
+/// cache-busting dance (unregister service workers + `location.reload()`).
+/// Reachable from JS after an update finishes, and from Rust via
+/// [`reload_main_window`] once the updater itself drives a reload.
+/// Only Windows (WebView2) and Linux (WebKitGTK) are handled; on other
+/// platforms the call returns `Ok` without reloading.
+#[tauri::command]
+pub fn reload_webview(app: AppHandle) -> Result<(), String> {
+    let window = app.get_webview_window("main").ok_or("no main window")?;
+    reload(&window)
+}
+
+pub fn reload_main_window(app: &AppHandle) -> Result<(), String> {
+    let window = app.get_webview_window("main").ok_or("no main window")?;
+    reload(&window)
+}
+
+fn reload(window: &WebviewWindow) -> Result<(), String> {
+    window
+        .with_webview(|webview| {
+            #[cfg(target_os = "windows")]
+            {
+                // SAFETY: CoreWebView2() and CoreWebView2::Reload() are plain
+                // COM calls (Reload() just queues a navigation); no lifetime
+                // requirements beyond the webview being alive, which it is
+                // for the duration of this closure.
+                unsafe {
+                    if let Ok(core) = webview.controller().CoreWebView2() {
+                        let _ = core.Reload();
+                    }
+                }
+            }
+            #[cfg(target_os = "linux")]
+            {
+                use webkit2gtk::WebViewExt;
+                webview.inner().reload();
+            }
+        })
+        .map_err(|e| e.to_string())
+}